@@ -1,21 +1,271 @@
 #![warn(clippy::pedantic, clippy::nursery, clippy::all, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions, clippy::module_name_repetitions)]
 
-use anyhow::Result;
-use arti_client::{TorClient, TorClientConfig};
-use axum::{routing::get, Router};
+use std::{
+	collections::HashMap,
+	fs::File,
+	future::Future,
+	io::BufReader,
+	path::{Path, PathBuf},
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use arc_swap::ArcSwap;
+use arti_client::{DataStream, TorAddr, TorClient, TorClientConfig};
+use axum::{
+	extract::{Query, State},
+	http::{header, HeaderMap, StatusCode},
+	response::{IntoResponse, Redirect},
+	routing::get,
+	Router,
+};
 use futures::StreamExt;
-use hyper::{body::Incoming, Request};
-use hyper_util::rt::{TokioExecutor, TokioIo};
-use native_tls::Identity;
+use http_body_util::{BodyExt, Full};
+use hyper::{body::Bytes, body::Incoming, Request, Uri};
+use hyper_util::{
+	client::legacy::{
+		connect::{Connected as HyperConnected, Connection},
+		Client,
+	},
+	rt::{TokioExecutor, TokioIo},
+};
+use rustls::{server::ResolvesServerCert, sign::CertifiedKey, ServerConfig};
 use safelog::sensitive;
-use tokio_native_tls::TlsAcceptor;
+use tokio::{
+	io::{AsyncRead, AsyncWrite, ReadBuf},
+	task::JoinSet,
+};
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
 use tor_cell::relaycell::msg::Connected;
 use tor_hsservice::{config::OnionServiceConfigBuilder, StreamRequest};
 use tor_proto::stream::IncomingStreamRequest;
 use tor_rtcompat::tokio::TokioNativeTlsRuntime;
 use tower_service::Service;
 
+/// Where the TLS certificate and key are read from (and re-read from, on
+/// reload). Unlike the old `include_bytes!`-embedded PEM, rotating the cert
+/// on disk is picked up without a rebuild.
+const CERT_PATH: &str = "self_signed_certs/cert.pem";
+const KEY_PATH: &str = "self_signed_certs/key.pem";
+
+/// How often to check the cert/key files for changes.
+const CERT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A `ResolvesServerCert` whose underlying key can be swapped out atomically,
+/// so the cert-reload task can rotate it without touching the `ServerConfig`
+/// or disturbing in-flight handshakes.
+struct ReloadableCertResolver {
+	current: ArcSwap<CertifiedKey>,
+}
+
+impl std::fmt::Debug for ReloadableCertResolver {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ReloadableCertResolver").finish_non_exhaustive()
+	}
+}
+
+impl ResolvesServerCert for ReloadableCertResolver {
+	fn resolve(&self, _client_hello: rustls::server::ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+		Some(self.current.load_full())
+	}
+}
+
+/// Loads a certificate chain and private key from PEM files and turns them
+/// into a `CertifiedKey` rustls can serve.
+fn load_certified_key(cert_path: &Path, key_path: &Path) -> Result<CertifiedKey> {
+	let mut cert_reader = BufReader::new(File::open(cert_path)?);
+	let chain = rustls_pemfile::certs(&mut cert_reader).collect::<std::result::Result<Vec<_>, _>>()?;
+
+	let mut key_reader = BufReader::new(File::open(key_path)?);
+	let key = rustls_pemfile::private_key(&mut key_reader)?.ok_or_else(|| anyhow::anyhow!("no private key found in {}", key_path.display()))?;
+
+	let signing_key = rustls::crypto::ring::sign::any_supported_type(&key)?;
+
+	Ok(CertifiedKey::new(chain, signing_key))
+}
+
+/// The most recent modification time of either the cert or the key file, so
+/// a rotation of just one of the two still triggers a reload.
+fn newest_mtime(cert_path: &Path, key_path: &Path) -> Option<std::time::SystemTime> {
+	let cert_modified = std::fs::metadata(cert_path).and_then(|m| m.modified()).ok();
+	let key_modified = std::fs::metadata(key_path).and_then(|m| m.modified()).ok();
+
+	cert_modified.into_iter().chain(key_modified).max()
+}
+
+/// Polls the cert/key files for changes and swaps a freshly loaded
+/// `CertifiedKey` into `resolver` whenever the newer of the two files'
+/// modification times changes (contents are not compared), so
+/// new connections pick up rotated certificates without a restart.
+fn spawn_cert_reload_task(resolver: Arc<ReloadableCertResolver>, cert_path: PathBuf, key_path: PathBuf) {
+	tokio::spawn(async move {
+		let mut last_modified = newest_mtime(&cert_path, &key_path);
+
+		loop {
+			tokio::time::sleep(CERT_POLL_INTERVAL).await;
+
+			let Some(modified) = newest_mtime(&cert_path, &key_path) else {
+				continue;
+			};
+
+			if last_modified == Some(modified) {
+				continue;
+			}
+
+			match load_certified_key(&cert_path, &key_path) {
+				Ok(key) => {
+					resolver.current.store(Arc::new(key));
+					last_modified = Some(modified);
+					eprintln!("reloaded TLS certificate from {} / {}", cert_path.display(), key_path.display());
+				}
+				Err(err) => eprintln!("failed to reload TLS certificate from {} / {}: {err}", cert_path.display(), key_path.display()),
+			}
+		}
+	});
+}
+
+/// Shared state handed to the routes of the main `app` router (not to the
+/// redirect or status routers in `build_sites`) so handlers can originate their
+/// own anonymized connections over the same bootstrapped `TorClient` that
+/// serves the onion service. `http_client` is how routes reach that
+/// `TorClient`: it's built on top of `ArtiHttpConnector`, which calls
+/// `client.connect` under the hood.
+#[derive(Clone)]
+struct AppState {
+	http_client: Client<ArtiHttpConnector, Full<Bytes>>,
+}
+
+/// Wraps a bootstrapped `TorClient` so it can be used as the connector for a
+/// `hyper_util` legacy client, turning `arti_client::DataStream`s into
+/// connections that hyper knows how to drive.
+#[derive(Clone)]
+struct ArtiHttpConnector {
+	client: TorClient<TokioNativeTlsRuntime>,
+}
+
+impl ArtiHttpConnector {
+	const fn new(client: TorClient<TokioNativeTlsRuntime>) -> Self {
+		Self { client }
+	}
+}
+
+impl Service<Uri> for ArtiHttpConnector {
+	type Response = ArtiHttpStream;
+	type Error = anyhow::Error;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, uri: Uri) -> Self::Future {
+		let client = self.client.clone();
+
+		Box::pin(async move {
+			// We only hand back a bare `DataStream`, with no TLS layered on top,
+			// so an `https` target would silently speak cleartext to a TLS port.
+			// Until this connector grows a TLS client layer, only plain `http`
+			// (including over onion services) is supported.
+			if uri.scheme_str() == Some("https") {
+				anyhow::bail!("uri {uri} requests https, but ArtiHttpConnector only speaks plaintext http over Tor");
+			}
+
+			let host = uri.host().ok_or_else(|| anyhow::anyhow!("uri {uri} has no host"))?;
+			let port = uri.port_u16().unwrap_or(80);
+			let addr = TorAddr::from((host, port))?;
+
+			let stream = client.connect(addr).await?;
+			Ok(ArtiHttpStream(TokioIo::new(stream)))
+		})
+	}
+}
+
+/// A `DataStream` dressed up as something hyper's legacy client will accept:
+/// `AsyncRead`/`AsyncWrite` so it can carry bytes, and `Connection` so the
+/// client can ask what it's connected to.
+struct ArtiHttpStream(TokioIo<DataStream>);
+
+impl AsyncRead for ArtiHttpStream {
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+		Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+	}
+}
+
+impl AsyncWrite for ArtiHttpStream {
+	fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+		Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+		Pin::new(&mut self.get_mut().0).poll_flush(cx)
+	}
+
+	fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+		Pin::new(&mut self.get_mut().0).poll_shutdown(cx)
+	}
+}
+
+impl Connection for ArtiHttpStream {
+	fn connected(&self) -> HyperConnected {
+		HyperConnected::new()
+	}
+}
+
+/// Whether a port's traffic should be handed to its `Router` as-is or
+/// TLS-terminated first.
+#[derive(Clone, Copy)]
+enum TlsPolicy {
+	Plaintext,
+	Tls,
+}
+
+/// What to serve on one accepted port of one onion service.
+#[derive(Clone)]
+struct PortEntry {
+	router: Router,
+	tls: TlsPolicy,
+}
+
+/// One onion service and the routing table for the ports it accepts.
+struct Site {
+	nickname: &'static str,
+	routes: Arc<HashMap<u16, PortEntry>>,
+}
+
+/// A tiny router that 308-redirects every request on a plaintext port over
+/// to the TLS port of the same host.
+fn redirect_to_tls_router(to_port: u16) -> Router {
+	Router::new().fallback(move |headers: HeaderMap, uri: Uri| async move {
+		let host = headers.get(header::HOST).and_then(|h| h.to_str().ok()).unwrap_or("onion").split(':').next().unwrap_or("onion").to_owned();
+
+		Redirect::permanent(&format!("https://{host}:{to_port}{uri}"))
+	})
+}
+
+/// The sites this process hosts, each its own onion service with its own
+/// per-port routing table. Add an entry here to host another site on its own
+/// onion address without touching the accept loop.
+fn build_sites(app: Router) -> Vec<Site> {
+	vec![
+		Site {
+			nickname: "allium-ampeloprasum",
+			routes: Arc::new(HashMap::from([
+				(80, PortEntry { router: redirect_to_tls_router(443), tls: TlsPolicy::Plaintext }),
+				(443, PortEntry { router: app, tls: TlsPolicy::Tls }),
+			])),
+		},
+		Site {
+			nickname: "allium-sativum",
+			routes: Arc::new(HashMap::from([(80, PortEntry { router: Router::new().route("/", get(|| async { "status: ok" })), tls: TlsPolicy::Plaintext })])),
+		},
+	]
+}
+
 #[tokio::main]
 async fn main() {
 	// Make sure you read doc/OnionService.md to extract your Onion service hostname
@@ -25,8 +275,12 @@ async fn main() {
 	// variable to actually see much; also try =debug for more detailed logging.)
 	tracing_subscriber::fmt::init();
 
-	// Initialize web server data, if you need to
-	//let handler = Arc::new(WebHandler { shutdown: CancellationToken::new() });
+	rustls::crypto::ring::default_provider().install_default().expect("failed to install default rustls crypto provider");
+
+	// Tripped by the signal listener below; every in-flight connection gets a
+	// clone so it can stop accepting new streams and start draining.
+	let shutdown = CancellationToken::new();
+	spawn_shutdown_listener(shutdown.clone());
 
 	// The client config includes things like where to store persistent Tor network
 	// state. The defaults provided are the same as the Arti standalone
@@ -39,91 +293,236 @@ async fn main() {
 	let client = TorClient::with_runtime(TokioNativeTlsRuntime::current().unwrap());
 	let client = client.config(config).create_bootstrapped().await.unwrap();
 
-	let svc_cfg = OnionServiceConfigBuilder::default().nickname("allium-ampeloprasum".parse().unwrap()).build().unwrap();
-	let (service, request_stream) = client.launch_onion_service(svc_cfg).unwrap();
+	let cert_resolver = Arc::new(ReloadableCertResolver { current: ArcSwap::from_pointee(load_certified_key(Path::new(CERT_PATH), Path::new(KEY_PATH)).unwrap()) });
 
-	let service_name = service.onion_name().unwrap().to_string();
-	eprintln!("service name: {service_name}");
+	let mut server_config = ServerConfig::builder().with_no_client_auth().with_cert_resolver(cert_resolver.clone());
+	// Offer h2 first: multiplexing several requests over one onion circuit is
+	// a bigger win than usual given Tor's per-circuit latency.
+	server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+	let tls_acceptor = TlsAcceptor::from(Arc::new(server_config));
 
-	let c = include_bytes!("../self_signed_certs/cert.pem");
-	let k = include_bytes!("../self_signed_certs/key.pem");
-	let cert = Identity::from_pkcs8(c, k).unwrap();
-	let tls_acceptor = TlsAcceptor::from(native_tls::TlsAcceptor::builder(cert).build().unwrap());
+	spawn_cert_reload_task(cert_resolver, PathBuf::from(CERT_PATH), PathBuf::from(KEY_PATH));
 
 	eprintln!("created tls acceptor");
 
-	let stream_requests = tor_hsservice::handle_rend_requests(request_stream);
+	let state = Arc::new(AppState { http_client: Client::builder(TokioExecutor::new()).build(ArtiHttpConnector::new(client.clone())) });
+
+	let app = Router::new().route("/", get(|| async { "Hello, World!" })).route("/fetch", get(fetch_over_tor)).with_state(state);
 
-	tokio::pin!(stream_requests);
+	let sites = build_sites(app);
+
+	// Launch every configured onion service and tag its request stream with
+	// the site's index, so the merged accept loop below can look back up
+	// which routing table to use.
+	let mut services = Vec::with_capacity(sites.len());
+	let mut tagged_streams = Vec::with_capacity(sites.len());
+
+	for (index, site) in sites.iter().enumerate() {
+		let svc_cfg = OnionServiceConfigBuilder::default().nickname(site.nickname.parse().unwrap()).build().unwrap();
+		let (service, request_stream) = client.launch_onion_service(svc_cfg).unwrap();
+
+		eprintln!("service {} name: {}", site.nickname, service.onion_name().unwrap());
+
+		let stream: Pin<Box<dyn futures::Stream<Item = (usize, StreamRequest)> + Send>> = Box::pin(tor_hsservice::handle_rend_requests(request_stream).map(move |stream_request| (index, stream_request)));
+
+		services.push(service);
+		tagged_streams.push(stream);
+	}
+
+	let mut stream_requests = futures::stream::select_all(tagged_streams);
 	eprintln!("ready to serve connections");
 
-	let app = Router::new().route("/", get(|| async { "Hello, World!" }));
+	let mut in_flight = JoinSet::new();
 
-	while let Some(stream_request) = stream_requests.next().await {
-		// incoming connection
-		//let handler = handler.clone();
-		let tls_acceptor = tls_acceptor.clone();
-		let app = app.clone();
+	loop {
+		tokio::select! {
+			next = stream_requests.next() => {
+				let Some((site_index, stream_request)) = next else { break };
 
-		eprintln!("received connection");
+				let tls_acceptor = tls_acceptor.clone();
+				let routes = Arc::clone(&sites[site_index].routes);
+				let shutdown = shutdown.clone();
 
-		tokio::spawn(async move {
-			let request = stream_request.request().clone();
+				eprintln!("received connection for site {site_index}");
 
-			eprintln!("handling connection");
-			let result = handle_stream_request(stream_request, tls_acceptor.clone(), app.clone()).await;
+				in_flight.spawn(async move {
+					let request = stream_request.request().clone();
 
-			match result {
-				Ok(()) => {}
-				Err(err) => {
-					eprintln!("error serving connection {:?}: {}", sensitive(request), err);
-				}
+					eprintln!("handling connection");
+					let result = handle_stream_request(stream_request, tls_acceptor, routes, shutdown).await;
+
+					if let Err(err) = result {
+						eprintln!("error serving connection {:?}: {}", sensitive(request), err);
+					}
+				});
+			}
+			() = shutdown.cancelled() => {
+				eprintln!("shutdown requested, no longer accepting new connections");
+				break;
 			}
-		});
+		}
+	}
+
+	eprintln!("draining {} in-flight connection(s)", in_flight.len());
+	if tokio::time::timeout(DRAIN_TIMEOUT, async { while in_flight.join_next().await.is_some() {} }).await.is_err() {
+		eprintln!("drain timeout elapsed, aborting {} remaining connection(s)", in_flight.len());
+		in_flight.shutdown().await;
 	}
 
-	drop(service);
-	eprintln!("onion service exited cleanly");
+	drop(services);
+	eprintln!("onion services exited cleanly");
+}
+
+/// How long to wait for in-flight requests to finish after a shutdown signal
+/// before aborting them outright.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Installs a `ctrl_c` (and, on unix, `SIGTERM`) listener that trips
+/// `shutdown` once either signal arrives.
+fn spawn_shutdown_listener(shutdown: CancellationToken) {
+	tokio::spawn(async move {
+		let ctrl_c = async {
+			let _ = tokio::signal::ctrl_c().await;
+		};
+
+		#[cfg(unix)]
+		let terminate = async {
+			let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).expect("failed to install SIGTERM handler");
+			sigterm.recv().await;
+		};
+		#[cfg(not(unix))]
+		let terminate = std::future::pending::<()>();
+
+		tokio::select! {
+			() = ctrl_c => eprintln!("received ctrl-c"),
+			() = terminate => eprintln!("received SIGTERM"),
+		}
+
+		shutdown.cancel();
+	});
 }
 
-async fn handle_stream_request(stream_request: StreamRequest, tls_acceptor: TlsAcceptor, app: Router) -> Result<()> {
-	match stream_request.request() {
-		IncomingStreamRequest::Begin(begin) if begin.port() == 80 || begin.port() == 443 => {
-			eprintln!("begin request");
-			let onion_service_stream = stream_request.accept(Connected::new_empty()).await.unwrap();
+async fn handle_stream_request(stream_request: StreamRequest, tls_acceptor: TlsAcceptor, routes: Arc<HashMap<u16, PortEntry>>, shutdown: CancellationToken) -> Result<()> {
+	let port = match stream_request.request() {
+		IncomingStreamRequest::Begin(begin) => begin.port(),
+		_ => {
+			eprintln!("rejecting non-Begin request: {:?}", stream_request.request());
+			stream_request.shutdown_circuit()?;
+			return Ok(());
+		}
+	};
+
+	let Some(entry) = routes.get(&port).cloned() else {
+		eprintln!("rejecting request on unmapped port {port}: {:?}", stream_request.request());
+		stream_request.shutdown_circuit()?;
+		return Ok(());
+	};
 
-			eprintln!("onion_service stream");
+	eprintln!("begin request on port {port}");
+	let onion_service_stream = stream_request.accept(Connected::new_empty()).await.context("failed to accept onion service stream")?;
 
-			//let onion_service_stream = TlsPrepStream { stream:
-			// Arc::new(TokioMutex::new(onion_service_stream)) };
-			let tls_onion_service_stream = tls_acceptor.accept(onion_service_stream).await.unwrap();
+	eprintln!("onion_service stream");
+
+	match entry.tls {
+		TlsPolicy::Plaintext => {
+			serve_auto(TokioIo::new(onion_service_stream), entry.router, shutdown).await;
+		}
+		TlsPolicy::Tls => {
+			let tls_onion_service_stream = tls_acceptor.accept(onion_service_stream).await.context("TLS handshake failed")?;
 
 			eprintln!("tls_onion_service_stream");
 
+			// ALPN was negotiated during the handshake above, so we already know
+			// which protocol the client wants rather than having to sniff the
+			// first bytes off the wrapped onion-service stream.
+			let alpn_protocol = tls_onion_service_stream.get_ref().1.alpn_protocol().map(<[u8]>::to_vec);
+
 			let stream = TokioIo::new(tls_onion_service_stream);
 
-			// Hyper also has its own `Service` trait and doesn't use tower. We can use
-			// `hyper::service::service_fn` to create a hyper `Service` that calls our app
-			// through `tower::Service::call`.
-			let hyper_service = hyper::service::service_fn(move |request: Request<Incoming>| {
-				// We have to clone `tower_service` because hyper's `Service` uses `&self`
-				// whereas tower's `Service` requires `&mut self`.
-				//
-				// We don't need to call `poll_ready` since `Router` is always ready.
-				app.clone().call(request)
-			});
+			if alpn_protocol.as_deref() == Some(b"h2") {
+				eprintln!("serving h2 (negotiated via ALPN)");
+				serve_h2(stream, entry.router, shutdown).await;
+			} else {
+				eprintln!("serving http/1.1 (negotiated via ALPN, or none offered)");
+				serve_auto(stream, entry.router, shutdown).await;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Drives an HTTP/2-only connection, built with `hyper`'s `h2`-specific
+/// builder because ALPN has already told us the client wants h2.
+async fn serve_h2(io: impl hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static, app: Router, shutdown: CancellationToken) {
+	let hyper_service = hyper::service::service_fn(move |request: Request<Incoming>| app.clone().call(request));
+
+	let conn = hyper::server::conn::http2::Builder::new(TokioExecutor::new()).serve_connection(io, hyper_service);
+	let mut conn = std::pin::pin!(conn);
+
+	tokio::select! {
+		ret = conn.as_mut() => {
+			if let Err(err) = ret {
+				eprintln!("error serving h2 connection: {err}");
+			}
+		}
+		() = shutdown.cancelled() => {
+			eprintln!("shutdown signalled mid-request, draining this h2 connection");
+			conn.as_mut().graceful_shutdown();
+
+			if let Err(err) = conn.await {
+				eprintln!("error draining h2 connection: {err}");
+			}
+		}
+	}
+}
 
-			let ret = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new()).serve_connection_with_upgrades(stream, hyper_service).await;
+/// Drives a connection with `hyper_util`'s protocol-detecting builder, used
+/// for plaintext ports and for TLS ports that didn't negotiate h2.
+async fn serve_auto(io: impl hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static, app: Router, shutdown: CancellationToken) {
+	let hyper_service = hyper::service::service_fn(move |request: Request<Incoming>| app.clone().call(request));
 
+	let conn = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new()).serve_connection_with_upgrades(io, hyper_service);
+	let mut conn = std::pin::pin!(conn);
+
+	tokio::select! {
+		ret = conn.as_mut() => {
 			if let Err(err) = ret {
 				eprintln!("error serving connection: {err}");
 			}
 		}
-		_ => {
-			eprintln!("rejecting request: {:?}", stream_request.request());
-			stream_request.shutdown_circuit()?;
+		() = shutdown.cancelled() => {
+			eprintln!("shutdown signalled mid-request, draining this connection");
+			conn.as_mut().graceful_shutdown();
+
+			if let Err(err) = conn.await {
+				eprintln!("error draining connection: {err}");
+			}
 		}
 	}
+}
 
-	Ok(())
+#[derive(serde::Deserialize)]
+struct FetchQuery {
+	url: String,
+}
+
+/// Demo route showing the example is now a bidirectional Tor peer: it issues
+/// an outbound GET through the same `TorClient` that serves the onion
+/// service, and relays the response body back to the caller.
+async fn fetch_over_tor(State(state): State<Arc<AppState>>, Query(query): Query<FetchQuery>) -> impl IntoResponse {
+	let uri: Uri = match query.url.parse() {
+		Ok(uri) => uri,
+		Err(err) => return (StatusCode::BAD_REQUEST, format!("invalid url: {err}")),
+	};
+
+	let request = Request::builder().uri(uri).body(Full::new(Bytes::new())).unwrap();
+
+	match state.http_client.request(request).await {
+		Ok(response) => match response.into_body().collect().await {
+			Ok(body) => (StatusCode::OK, String::from_utf8_lossy(&body.to_bytes()).into_owned()),
+			Err(err) => (StatusCode::BAD_GATEWAY, format!("failed to read response: {err}")),
+		},
+		Err(err) => (StatusCode::BAD_GATEWAY, format!("request failed: {err}")),
+	}
 }